@@ -0,0 +1,59 @@
+#![no_main]
+
+use std::io::{self, Cursor};
+
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use lzma_rust2::{LzmaOptions, LzmaReader, LzmaWriter, MfType};
+
+#[derive(Debug)]
+struct Input {
+    options: LzmaOptions,
+    data: Vec<u8>,
+}
+
+impl<'a> arbitrary::Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> Result<Self, arbitrary::Error> {
+        let preset = u32::arbitrary(u)? % 10;
+        let mut options = LzmaOptions::with_preset(preset);
+        options.mf = if bool::arbitrary(u)? {
+            MfType::Bt4
+        } else {
+            MfType::Hc4
+        };
+        // Keep the dictionary small so the fuzzer spends its time on the
+        // range coder instead of allocating huge buffers.
+        options.dict_size = options.dict_size.min(1 << 16);
+
+        let data = Vec::arbitrary(u)?;
+        Ok(Self { options, data })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<u8>::size_hint(depth)
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let props = input.options.get_props();
+    let dict_size = input.options.dict_size;
+
+    let mut compressed = Vec::new();
+    let mut writer =
+        LzmaWriter::new_no_header(&mut compressed, &input.options, false).unwrap();
+    io::copy(&mut Cursor::new(&input.data), &mut writer).unwrap();
+    writer.finish().unwrap();
+
+    let mut reader = LzmaReader::new_with_props(
+        compressed.as_slice(),
+        input.data.len() as u64,
+        props,
+        dict_size,
+        None,
+    )
+    .unwrap();
+    let mut decompressed = Vec::new();
+    io::copy(&mut reader, &mut decompressed).unwrap();
+
+    assert_eq!(decompressed, input.data);
+});
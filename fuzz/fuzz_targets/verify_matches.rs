@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use lzma_rust2::{fuzz_check_match_finder, MfType};
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Input {
+    use_bt4: bool,
+    dict_size: u32,
+    data: Vec<u8>,
+}
+
+// Drives both match finders across arbitrary input and asserts that every
+// reported match is actually present in the window.
+fuzz_target!(|input: Input| {
+    let mf = if input.use_bt4 { MfType::Bt4 } else { MfType::Hc4 };
+    // Keep the dictionary between 4 KiB and 64 KiB. The window buffer still
+    // holds over 256 KiB of reserve on top of that, so only inputs larger
+    // than about 260 KiB cross a `move_window` boundary.
+    let dict_size = (input.dict_size % (64 << 10)).max(4 << 10);
+
+    assert!(fuzz_check_match_finder(mf, dict_size, &input.data));
+});
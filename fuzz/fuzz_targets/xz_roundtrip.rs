@@ -0,0 +1,46 @@
+#![no_main]
+
+use std::io;
+
+use libfuzzer_sys::{arbitrary, fuzz_target};
+use lzma_rust2::{LzmaOptions, MfType, XzReader, XzWriter};
+
+#[derive(Debug)]
+struct Input {
+    options: LzmaOptions,
+    data: Vec<u8>,
+}
+
+impl<'a> arbitrary::Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> Result<Self, arbitrary::Error> {
+        let preset = u32::arbitrary(u)? % 10;
+        let mut options = LzmaOptions::with_preset(preset);
+        options.mf = if bool::arbitrary(u)? {
+            MfType::Bt4
+        } else {
+            MfType::Hc4
+        };
+        options.dict_size = options.dict_size.min(1 << 16);
+
+        let data = Vec::arbitrary(u)?;
+        Ok(Self { options, data })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<u8>::size_hint(depth)
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let mut compressed = Vec::new();
+    let mut writer = XzWriter::new(&mut compressed, &input.options).unwrap();
+    io::copy(&mut io::Cursor::new(&input.data), &mut writer).unwrap();
+    writer.finish().unwrap();
+
+    let mut reader = XzReader::new(compressed.as_slice(), true);
+    let mut decompressed = Vec::new();
+    io::copy(&mut reader, &mut decompressed).unwrap();
+
+    assert_eq!(decompressed, input.data);
+});
@@ -0,0 +1,127 @@
+//! Minimal IO abstraction used in place of `std::io` when the `std` feature
+//! is disabled, so that [`crate::Lzma2Reader`], [`crate::lz::LzDecoder`]
+//! (via its `alloc`-only internals), and [`crate::lzma2_reader::get_memory_usage`]
+//! can be used from `no_std` embedded and WASM consumers with only `alloc`.
+//!
+//! This mirrors the `io_nostd` module ruzstd/zstd-rs added for the same
+//! reason: a `Read`/`Write` pair that doesn't depend on `std::io`, plus an
+//! allocation-free error type. With the `std` feature enabled, `Read` and
+//! `Write` are re-exports of `std::io::Read`/`std::io::Write`, so existing
+//! `std` users see no change at all.
+
+#[cfg(feature = "std")]
+pub use std::io::{Read, Write};
+
+/// The reason an IO operation failed, without an allocated message.
+///
+/// Every call site in this crate passes a `&'static str` literal describing
+/// the failure, so `Error` never needs to own or format a string - it just
+/// carries the literal through.
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    InvalidData,
+    InvalidInput,
+    UnexpectedEof,
+    Other,
+}
+
+/// An allocation-free error type used in place of [`std::io::Error`] when
+/// the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    msg: &'static str,
+}
+
+#[cfg(not(feature = "std"))]
+impl Error {
+    pub const fn new(kind: ErrorKind, msg: &'static str) -> Self {
+        Self { kind, msg }
+    }
+
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub const fn message(&self) -> &'static str {
+        self.msg
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.msg)
+    }
+}
+
+/// A minimal stand-in for [`std::io::Read`], implemented directly on top of
+/// `alloc`-only types when the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+pub trait Read {
+    fn read(&mut self, buf: &mut [u8]) -> crate::Result<usize>;
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> crate::Result<()> {
+        while !buf.is_empty() {
+            match self.read(buf)? {
+                0 => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ))
+                }
+                n => buf = &mut buf[n..],
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A minimal stand-in for [`std::io::Write`], implemented directly on top of
+/// `alloc`-only types when the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+pub trait Write {
+    fn write(&mut self, buf: &[u8]) -> crate::Result<usize>;
+
+    fn write_all(&mut self, mut buf: &[u8]) -> crate::Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf)? {
+                0 => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "failed to write whole buffer",
+                    ))
+                }
+                n => buf = &buf[n..],
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> crate::Result<()>;
+}
+
+#[cfg(not(feature = "std"))]
+impl Read for &[u8] {
+    fn read(&mut self, buf: &mut [u8]) -> crate::Result<usize> {
+        let len = buf.len().min(self.len());
+        let (head, tail) = self.split_at(len);
+        buf[..len].copy_from_slice(head);
+        *self = tail;
+        Ok(len)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Write for alloc::vec::Vec<u8> {
+    fn write(&mut self, buf: &[u8]) -> crate::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> crate::Result<()> {
+        Ok(())
+    }
+}
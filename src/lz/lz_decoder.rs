@@ -2,6 +2,67 @@ use alloc::{vec, vec::Vec};
 
 use crate::{error_invalid_data, error_other, Read};
 
+/// Width, in bytes, of the chunked copy used by [`wildcopy`].
+#[cfg(feature = "unchecked-decode")]
+const WILDCOPY_WIDTH: usize = 8;
+
+/// Copies `len` bytes from `buf[src..]` to `buf[dst..]` in `WILDCOPY_WIDTH`-
+/// byte chunks, which may write up to `WILDCOPY_WIDTH - 1` bytes past
+/// `dst + len`.
+///
+/// This trades a few bytes of silently-overwritten (but never read) slack
+/// for doing `len / WILDCOPY_WIDTH` wide copies instead of `len` byte-sized
+/// ones, mirroring lz4_flex's `wildcopy`/`fastcpy` split.
+///
+/// # Safety
+/// The caller must ensure `src + len <= buf.len()`,
+/// `dst + len + (WILDCOPY_WIDTH - 1) <= buf.len()`, and `dst >= src +
+/// WILDCOPY_WIDTH` (a per-chunk overlap check: each chunk reads
+/// `[src + i, src + i + WILDCOPY_WIDTH)` and writes
+/// `[dst + i, dst + i + WILDCOPY_WIDTH)` for the same `i`, which only avoids
+/// overlapping `copy_nonoverlapping` ranges when the `src`/`dst` gap is at
+/// least one full chunk wide).
+#[cfg(feature = "unchecked-decode")]
+unsafe fn wildcopy(buf: &mut [u8], src: usize, dst: usize, len: usize) {
+    let base = buf.as_mut_ptr();
+    let mut copied = 0;
+    while copied < len {
+        core::ptr::copy_nonoverlapping(
+            base.add(src + copied),
+            base.add(dst + copied),
+            WILDCOPY_WIDTH,
+        );
+        copied += WILDCOPY_WIDTH;
+    }
+}
+
+/// Same as [`wildcopy`], but between two distinct buffers, copying
+/// `src[src_off..]` to `dst[dst_off..]`.
+///
+/// Takes the full buffers plus offsets, rather than pre-sliced
+/// `&[src_off..][..len]` views, so the raw pointers keep the full buffers'
+/// provenance: deriving a pointer from a sub-slice and then offsetting past
+/// its end (as the `WILDCOPY_WIDTH - 1` overshoot does) is out-of-bounds
+/// even when the larger backing allocation would cover it.
+///
+/// # Safety
+/// The caller must ensure `src_off + len + (WILDCOPY_WIDTH - 1) <=
+/// src.len()` and `dst_off + len + (WILDCOPY_WIDTH - 1) <= dst.len()`.
+#[cfg(feature = "unchecked-decode")]
+unsafe fn wildcopy_between(src: &[u8], src_off: usize, dst: &mut [u8], dst_off: usize, len: usize) {
+    let src_ptr = src.as_ptr();
+    let dst_ptr = dst.as_mut_ptr();
+    let mut copied = 0;
+    while copied < len {
+        core::ptr::copy_nonoverlapping(
+            src_ptr.add(src_off + copied),
+            dst_ptr.add(dst_off + copied),
+            WILDCOPY_WIDTH,
+        );
+        copied += WILDCOPY_WIDTH;
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct LzDecoder {
     buf: Vec<u8>,
@@ -116,12 +177,60 @@ impl LzDecoder {
 
         if dist >= left {
             // No overlap possible. We can copy directly.
+            #[cfg(feature = "unchecked-decode")]
+            if dist + 1 >= WILDCOPY_WIDTH
+                && self.full <= self.pos
+                && self.buf_size - self.pos >= left + WILDCOPY_WIDTH
+            {
+                // SAFETY: `back + left <= self.pos` (no overlap, since
+                // `dist >= left`), the `dist + 1 >= WILDCOPY_WIDTH` check
+                // guarantees no chunk's overshoot overlaps the destination
+                // (the gap between `back` and `self.pos` is `dist + 1`),
+                // and the headroom check guarantees the overshoot still
+                // lands inside `self.buf`. `self.full <= self.pos` means
+                // the buffer has not wrapped, so the overshoot bytes hold
+                // no dictionary history yet.
+                unsafe { wildcopy(&mut self.buf, back, self.pos, left) };
+                self.pos += left;
+                if self.full < self.pos {
+                    self.full = self.pos;
+                }
+                return Ok(());
+            }
             let (src_part, dst_part) = self.buf.split_at_mut(self.pos);
             dst_part[..left].copy_from_slice(&src_part[back..back + left]);
             self.pos += left;
         } else {
             loop {
                 let copy_size = left.min(self.pos - back);
+
+                // `copy_size <= self.pos - back`, so the source range always
+                // ends at or before `self.pos`: never overlapping with the
+                // destination. This is what lets the run be filled by
+                // exponential doubling (each step's destination becomes
+                // part of the next step's source).
+                #[cfg(feature = "unchecked-decode")]
+                if dist + 1 >= WILDCOPY_WIDTH
+                    && self.full <= self.pos
+                    && self.buf_size - self.pos >= copy_size + WILDCOPY_WIDTH
+                {
+                    // SAFETY: see the non-overlap comment above; the
+                    // `dist + 1 >= WILDCOPY_WIDTH` check guarantees no
+                    // chunk's overshoot overlaps the destination (the gap
+                    // between `back` and `self.pos` starts at `dist + 1`
+                    // and only grows across doubling steps), the headroom
+                    // check guarantees the overshoot stays inside
+                    // `self.buf`, and `self.full <= self.pos` means the
+                    // overshoot bytes hold no dictionary history yet.
+                    unsafe { wildcopy(&mut self.buf, back, self.pos, copy_size) };
+                    self.pos += copy_size;
+                    left -= copy_size;
+                    if left == 0 {
+                        break;
+                    }
+                    continue;
+                }
+
                 self.buf.copy_within(back..back + copy_size, self.pos);
                 self.pos += copy_size;
                 left -= copy_size;
@@ -144,11 +253,15 @@ impl LzDecoder {
         Ok(())
     }
 
+    /// Copies up to `len` bytes of uncompressed chunk data from `in_data`
+    /// into the dictionary buffer, returning how many bytes were actually
+    /// consumed (which is less than `len` whenever the buffer fills up
+    /// before `len` bytes have been read).
     pub(crate) fn copy_uncompressed<R: Read>(
         &mut self,
         mut in_data: R,
         len: usize,
-    ) -> crate::Result<()> {
+    ) -> crate::Result<usize> {
         let copy_size = (self.buf_size - self.pos).min(len);
         let buf = &mut self.buf[self.pos..(self.pos + copy_size)];
         in_data.read_exact(buf)?;
@@ -156,7 +269,7 @@ impl LzDecoder {
         if self.full < self.pos {
             self.full = self.pos;
         }
-        Ok(())
+        Ok(copy_size)
     }
 
     pub(crate) fn flush(&mut self, out: &mut [u8], out_off: usize) -> crate::Result<usize> {
@@ -166,16 +279,59 @@ impl LzDecoder {
             self.pos = 0;
         }
 
+        if self.start + copy_size > self.buf.len() {
+            return Err(error_invalid_data("invalid source range"));
+        }
+        if out_off + copy_size > out.len() {
+            return Err(error_invalid_data("invalid destination range"));
+        }
+
+        // The wildcopy path is only taken when both `self.buf` and `out`
+        // have at least `WILDCOPY_WIDTH` bytes of slack beyond `copy_size`;
+        // otherwise the exact-length copy below is used.
+        #[cfg(feature = "unchecked-decode")]
+        if self.buf_size - self.start >= copy_size + WILDCOPY_WIDTH
+            && out.len() - out_off >= copy_size + WILDCOPY_WIDTH
+        {
+            // SAFETY: the headroom check above guarantees both the read
+            // from `self.buf` and the write into `out` stay in bounds even
+            // with the wildcopy's overshoot. Both pointers are derived from
+            // the full buffers (not a pre-sliced view), so the overshoot
+            // stays within each allocation's provenance too.
+            unsafe { wildcopy_between(&self.buf, self.start, out, out_off, copy_size) };
+            self.start = self.pos;
+            return Ok(copy_size);
+        }
+
+        let src = &self.buf[self.start..(self.start + copy_size)];
+        let dst = &mut out[out_off..(out_off + copy_size)];
+        dst.copy_from_slice(src);
+
+        self.start = self.pos;
+
+        Ok(copy_size)
+    }
+
+    /// Same as [`Self::flush`], but writes the decoded bytes into a
+    /// [`bytes::BufMut`] sink instead of a plain output slice.
+    ///
+    /// This lets async/IO-heavy callers that already manage their output
+    /// buffer as a `BufMut` (e.g. a socket write buffer) avoid an extra
+    /// copy through an intermediate `&mut [u8]`.
+    #[cfg(feature = "bytes")]
+    pub(crate) fn flush_buf_mut(&mut self, out: &mut impl bytes::BufMut) -> crate::Result<usize> {
+        let copy_size = self.pos.saturating_sub(self.start);
+
+        if self.pos == self.buf_size {
+            self.pos = 0;
+        }
+
         let src = self
             .buf
             .get(self.start..(self.start + copy_size))
             .ok_or(error_invalid_data("invalid source range"))?;
 
-        let dst = out
-            .get_mut(out_off..(out_off + copy_size))
-            .ok_or(error_invalid_data("invalid destination range"))?;
-
-        dst.copy_from_slice(src);
+        out.put_slice(src);
 
         self.start = self.pos;
 
@@ -115,13 +115,14 @@ impl LzEncoder {
         match_len_max: u32,
         depth_limit: i32,
     ) -> Self {
+        let mf = MatchFinders::Hc4(Hc4::new(dict_size, nice_len, depth_limit));
         Self::new(
             dict_size,
             extra_size_before,
             extra_size_after,
             nice_len,
             match_len_max,
-            MatchFinders::Hc4(Hc4::new(dict_size, nice_len, depth_limit)),
+            mf,
         )
     }
 
@@ -133,13 +134,14 @@ impl LzEncoder {
         match_len_max: u32,
         depth_limit: i32,
     ) -> Self {
+        let mf = MatchFinders::Bt4(Bt4::new(dict_size, nice_len, depth_limit));
         Self::new(
             dict_size,
             extra_size_before,
             extra_size_after,
             nice_len,
             match_len_max,
-            MatchFinders::Bt4(Bt4::new(dict_size, nice_len, depth_limit)),
+            mf,
         )
     }
 
@@ -205,6 +207,18 @@ impl LzEncoder {
             }
         }
 
+        #[cfg(all(
+            feature = "std",
+            feature = "optimization",
+            target_arch = "wasm32",
+            target_feature = "simd128"
+        ))]
+        {
+            // SAFETY: `target_feature = "simd128"` is gated at compile time,
+            // so the intrinsics used below are always available here.
+            return unsafe { normalize_simd128(positions, norm_offset) };
+        }
+
         normalize_scalar(positions, norm_offset);
     }
 
@@ -234,6 +248,16 @@ impl LzEncoder {
         self.data.fill_window(input, &mut self.match_finder)
     }
 
+    /// Same as [`Self::fill_window`], but pulls bytes from a [`bytes::Buf`]
+    /// instead of a contiguous slice.
+    ///
+    /// This lets callers that already hold fragmented network/file buffers
+    /// feed the encoder without first linearizing them into a `Vec<u8>`.
+    #[cfg(feature = "bytes")]
+    pub(crate) fn fill_window_buf(&mut self, input: &mut impl bytes::Buf) -> usize {
+        self.data.fill_window_buf(input, &mut self.match_finder)
+    }
+
     pub(crate) fn set_flushing(&mut self) {
         self.data.set_flushing(&mut self.match_finder)
     }
@@ -306,6 +330,39 @@ impl LzEncoderData {
         len
     }
 
+    #[cfg(feature = "bytes")]
+    fn fill_window_buf(
+        &mut self,
+        input: &mut impl bytes::Buf,
+        match_finder: &mut dyn MatchFind,
+    ) -> usize {
+        debug_assert!(!self.finishing);
+        if self.read_pos >= (self.buf_size as i32 - self.keep_size_after as i32) {
+            self.move_window();
+        }
+
+        let limit = (self.buf_size as i32 - self.write_pos) as usize;
+        let mut copied = 0;
+        while copied < limit && input.has_remaining() {
+            let chunk = input.chunk();
+            if chunk.is_empty() {
+                break;
+            }
+            let take = chunk.len().min(limit - copied);
+            let d_start = self.write_pos as usize;
+            self.buf[d_start..d_start + take].copy_from_slice(&chunk[..take]);
+            input.advance(take);
+            self.write_pos += take as i32;
+            copied += take;
+        }
+
+        if self.write_pos >= self.keep_size_after as i32 {
+            self.read_limit = self.write_pos - self.keep_size_after as i32;
+        }
+        self.process_pending_bytes(match_finder);
+        copied
+    }
+
     fn process_pending_bytes(&mut self, match_finder: &mut dyn MatchFind) {
         if self.pending_size > 0 && self.read_pos < self.read_limit {
             self.read_pos -= self.pending_size as i32;
@@ -456,6 +513,11 @@ impl Deref for LzEncoder {
     }
 }
 
+/// Upper bound on the reserve space that [`get_buf_size`] adds beyond the
+/// before/after keep sizes, i.e. on how much new input the window can take
+/// in between `move_window` calls.
+const RESERVE_SIZE_MAX: u32 = 512 << 20;
+
 fn get_buf_size(
     dict_size: u32,
     extra_size_before: u32,
@@ -464,7 +526,7 @@ fn get_buf_size(
 ) -> u32 {
     let keep_size_before = extra_size_before + dict_size;
     let keep_size_after = extra_size_after + match_len_max;
-    let reserve_size = (dict_size / 2 + (256 << 10)).min(512 << 20);
+    let reserve_size = (dict_size / 2 + (256 << 10)).min(RESERVE_SIZE_MAX);
     keep_size_before + keep_size_after + reserve_size
 }
 
@@ -563,3 +625,89 @@ unsafe fn normalize_sse41(positions: &mut [i32], norm_offset: i32) {
 
     normalize_scalar(suffix, norm_offset);
 }
+
+/// Normalization implementation using WASM `simd128` for 128-bit SIMD
+/// processing, for `wasm32` targets running without a libc (browsers, edge
+/// runtimes) that would otherwise fall back to the scalar loop.
+#[cfg(all(
+    feature = "std",
+    feature = "optimization",
+    target_arch = "wasm32",
+    target_feature = "simd128"
+))]
+unsafe fn normalize_simd128(positions: &mut [i32], norm_offset: i32) {
+    use core::arch::wasm32::*;
+
+    // Create a 128-bit vector with the offset broadcast to all 4 lanes.
+    let norm_v = i32x4_splat(norm_offset);
+
+    // Split the slice into a 16-byte aligned middle part and unaligned ends.
+    let (prefix, chunks, suffix) = positions.align_to_mut::<v128>();
+
+    normalize_scalar(prefix, norm_offset);
+
+    for chunk in chunks {
+        let data = v128_load(chunk as *const v128);
+
+        // Perform saturated subtraction on 4 integers simultaneously.
+        let max_val = i32x4_max(data, norm_v);
+        let result = i32x4_sub(max_val, norm_v);
+
+        v128_store(chunk as *mut v128, result);
+    }
+
+    normalize_scalar(suffix, norm_offset);
+}
+
+/// Drives a match finder across an entire input, re-verifying
+/// [`LzEncoderData::verify_matches`] after every `find_matches` call,
+/// including across `fill_window`/`move_window` boundaries.
+///
+/// This is exposed only for the differential fuzz harness in `fuzz/` and is
+/// not part of the public API surface.
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub fn fuzz_check_match_finder(mf: MfType, dict_size: u32, input: &[u8]) -> bool {
+    const NICE_LEN: u32 = 64;
+    const MATCH_LEN_MAX: u32 = 273;
+    let depth_limit = 0;
+
+    let mut encoder = match mf {
+        MfType::Hc4 => LzEncoder::new_hc4(
+            dict_size,
+            0,
+            MATCH_LEN_MAX,
+            NICE_LEN,
+            MATCH_LEN_MAX,
+            depth_limit,
+        ),
+        MfType::Bt4 => LzEncoder::new_bt4(
+            dict_size,
+            0,
+            MATCH_LEN_MAX,
+            NICE_LEN,
+            MATCH_LEN_MAX,
+            depth_limit,
+        ),
+    };
+
+    let mut fed = 0;
+    while fed < input.len() {
+        fed += encoder.fill_window(&input[fed..]);
+        if fed == input.len() {
+            encoder.set_finishing();
+        }
+
+        while encoder.data.has_enough_data(0) {
+            encoder.find_matches();
+            if !encoder.verify_matches() {
+                return false;
+            }
+            if encoder.data.move_pos(1, 1) == 0 {
+                break;
+            }
+        }
+    }
+
+    true
+}
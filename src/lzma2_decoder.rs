@@ -0,0 +1,331 @@
+use alloc::vec::Vec;
+
+use super::{
+    decoder::LzmaDecoder,
+    error_invalid_input,
+    lz::LzDecoder,
+    range_dec::{RangeDecoder, RangeDecoderBuffer},
+    Read,
+};
+
+const COMPRESSED_SIZE_MAX: usize = 1 << 16;
+
+/// The result of a single [`Lzma2Decoder::decompress`] call.
+///
+/// Every variant carries the number of input bytes consumed and output
+/// bytes produced during that call, so callers can advance their own
+/// cursors regardless of which variant comes back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The decoder consumed all of `input` and needs more before it can
+    /// make further progress.
+    NeedMoreInput { consumed: usize, produced: usize },
+    /// The decoder filled all of `output` and needs a larger (or fresh)
+    /// buffer to continue.
+    NeedMoreOutput { consumed: usize, produced: usize },
+    /// The end-of-stream chunk (control byte `0x00`) was reached.
+    Finished { consumed: usize, produced: usize },
+}
+
+#[inline]
+fn get_dict_size(dict_size: u32) -> u32 {
+    if dict_size >= (u32::MAX - 15) {
+        return u32::MAX;
+    }
+
+    (dict_size + 15) & !15
+}
+
+/// A sans-io, push-based LZMA2 decoder.
+///
+/// Unlike [`crate::Lzma2Reader`], this never reads from or blocks on an I/O
+/// source. Instead, [`Self::decompress`] is driven by the caller with
+/// whatever input slice and output slice happen to be available, which
+/// suits async runtimes and other non-blocking callers. Internally it
+/// buffers a chunk header (the control byte plus up to five size/props
+/// bytes) and a chunk's compressed payload across calls whenever either
+/// straddles an `input` boundary, and otherwise tracks the exact same state
+/// as [`crate::Lzma2Reader`].
+///
+/// # Examples
+/// ```
+/// use lzma_rust2::{Lzma2Decoder, LzmaOptions, Status};
+///
+/// let compressed: Vec<u8> = vec![
+///     1, 0, 12, 72, 101, 108, 108, 111, 44, 32, 119, 111, 114, 108, 100, 33, 0,
+/// ];
+/// let mut decoder = Lzma2Decoder::new(LzmaOptions::DICT_SIZE_DEFAULT, None);
+/// let mut output = [0u8; 13];
+/// let status = decoder.decompress(&compressed, &mut output).unwrap();
+/// assert_eq!(status, Status::Finished { consumed: compressed.len(), produced: 13 });
+/// assert_eq!(&output[..], b"Hello, world!");
+/// ```
+pub struct Lzma2Decoder {
+    lz: LzDecoder,
+    rc: RangeDecoder<RangeDecoderBuffer>,
+    lzma: Option<LzmaDecoder>,
+    uncompressed_size: usize,
+    is_lzma_chunk: bool,
+    need_dict_reset: bool,
+    need_props: bool,
+    end_reached: bool,
+
+    /// Header bytes collected so far for the chunk currently being parsed;
+    /// empty once a full header has been parsed.
+    header_buf: Vec<u8>,
+    /// Compressed payload bytes collected so far for the LZMA chunk
+    /// currently being prepared; only used while `rc_ready` is `false`.
+    body_buf: Vec<u8>,
+    /// Target length of `body_buf`, i.e. the current chunk's compressed
+    /// size, set once its header has been parsed.
+    body_target: usize,
+    /// Whether `rc` has been `prepare`d with the current LZMA chunk's full
+    /// compressed payload yet.
+    rc_ready: bool,
+}
+
+impl Lzma2Decoder {
+    /// Creates a new LZMA2 decoder.
+    /// `dict_size` is the dictionary size in bytes.
+    pub fn new(dict_size: u32, preset_dict: Option<&[u8]>) -> Self {
+        let has_preset = preset_dict.as_ref().map(|a| !a.is_empty()).unwrap_or(false);
+        let lz = LzDecoder::new(get_dict_size(dict_size) as _, preset_dict);
+        let rc = RangeDecoder::new_buffer(COMPRESSED_SIZE_MAX as _);
+        Self {
+            lz,
+            rc,
+            lzma: None,
+            uncompressed_size: 0,
+            is_lzma_chunk: false,
+            need_dict_reset: !has_preset,
+            need_props: true,
+            end_reached: false,
+            header_buf: Vec::new(),
+            body_buf: Vec::new(),
+            body_target: 0,
+            rc_ready: false,
+        }
+    }
+
+    /// Decompresses as much of `input` into `output` as possible in one
+    /// call, returning how much of each was consumed/produced and why the
+    /// call stopped.
+    ///
+    /// Can be called repeatedly with fresh `input`/`output` slices; all
+    /// state needed to resume mid-chunk, mid-header, or mid-payload is kept
+    /// on `self`.
+    pub fn decompress(&mut self, input: &[u8], output: &mut [u8]) -> crate::Result<Status> {
+        let mut in_pos = 0;
+        let mut out_pos = 0;
+
+        loop {
+            if self.end_reached {
+                return Ok(Status::Finished {
+                    consumed: in_pos,
+                    produced: out_pos,
+                });
+            }
+
+            if self.uncompressed_size == 0 {
+                let (consumed, complete) = self.feed_header(&input[in_pos..])?;
+                in_pos += consumed;
+                if !complete {
+                    return Ok(Status::NeedMoreInput {
+                        consumed: in_pos,
+                        produced: out_pos,
+                    });
+                }
+                if self.end_reached {
+                    return Ok(Status::Finished {
+                        consumed: in_pos,
+                        produced: out_pos,
+                    });
+                }
+            }
+
+            if self.is_lzma_chunk && !self.rc_ready {
+                let (consumed, complete) = self.feed_body(&input[in_pos..]);
+                in_pos += consumed;
+                if !complete {
+                    return Ok(Status::NeedMoreInput {
+                        consumed: in_pos,
+                        produced: out_pos,
+                    });
+                }
+
+                let body = core::mem::take(&mut self.body_buf);
+                self.rc.prepare(&mut body.as_slice(), self.body_target)?;
+                self.rc_ready = true;
+            }
+
+            if out_pos == output.len() {
+                return Ok(Status::NeedMoreOutput {
+                    consumed: in_pos,
+                    produced: out_pos,
+                });
+            }
+
+            if self.is_lzma_chunk {
+                let copy_size_max = self.uncompressed_size.min(output.len() - out_pos);
+                self.lz.set_limit(copy_size_max);
+                if let Some(lzma) = self.lzma.as_mut() {
+                    lzma.decode(&mut self.lz, &mut self.rc)?;
+                }
+            } else {
+                let avail_in = input.len() - in_pos;
+                if avail_in == 0 {
+                    return Ok(Status::NeedMoreInput {
+                        consumed: in_pos,
+                        produced: out_pos,
+                    });
+                }
+                let copy_size_max = self
+                    .uncompressed_size
+                    .min(output.len() - out_pos)
+                    .min(avail_in);
+                let consumed = self
+                    .lz
+                    .copy_uncompressed(&input[in_pos..in_pos + copy_size_max], copy_size_max)?;
+                in_pos += consumed;
+            }
+
+            let copied = self.lz.flush(output, out_pos)?;
+            out_pos += copied;
+            self.uncompressed_size = self.uncompressed_size.saturating_sub(copied);
+
+            if self.uncompressed_size == 0 {
+                if !self.rc.is_finished() || self.lz.has_pending() {
+                    return Err(error_invalid_input("rc not finished or lz has pending"));
+                }
+                self.rc_ready = false;
+            }
+        }
+    }
+
+    /// Accumulates chunk header bytes from `input` into `header_buf`,
+    /// parsing it (via [`Self::parse_header`]) once complete.
+    ///
+    /// Returns `(consumed, complete)`: `consumed` is always accurate even
+    /// when `complete` is `false`, so the caller can advance its cursor
+    /// before reporting [`Status::NeedMoreInput`].
+    fn feed_header(&mut self, input: &[u8]) -> crate::Result<(usize, bool)> {
+        let mut consumed = 0;
+
+        if self.header_buf.is_empty() {
+            let Some(&control) = input.first() else {
+                return Ok((0, false));
+            };
+            self.header_buf.push(control);
+            consumed += 1;
+        }
+
+        let needed = header_len(self.header_buf[0])?;
+        while self.header_buf.len() < needed && consumed < input.len() {
+            self.header_buf.push(input[consumed]);
+            consumed += 1;
+        }
+
+        if self.header_buf.len() < needed {
+            return Ok((consumed, false));
+        }
+
+        self.parse_header()?;
+        self.header_buf.clear();
+        Ok((consumed, true))
+    }
+
+    // See the control byte table on `Lzma2Reader::decode_chunk_header`,
+    // which this mirrors field-for-field against a buffered header instead
+    // of a blocking reader.
+    fn parse_header(&mut self) -> crate::Result<()> {
+        let control = self.header_buf[0];
+
+        if control == 0x00 {
+            self.end_reached = true;
+            return Ok(());
+        }
+
+        if control >= 0xE0 || control == 0x01 {
+            self.need_props = true;
+            self.need_dict_reset = false;
+            self.lz.reset();
+        } else if self.need_dict_reset {
+            return Err(error_invalid_input("corrupted input data (LZMA2:0)"));
+        }
+
+        if control >= 0x80 {
+            self.is_lzma_chunk = true;
+            self.uncompressed_size = ((control & 0x1F) as usize) << 16;
+            self.uncompressed_size +=
+                (((self.header_buf[1] as usize) << 8) | self.header_buf[2] as usize) + 1;
+            let compressed_size =
+                (((self.header_buf[3] as usize) << 8) | self.header_buf[4] as usize) + 1;
+
+            if control >= 0xC0 {
+                self.need_props = false;
+                self.decode_props(self.header_buf[5])?;
+            } else if self.need_props {
+                return Err(error_invalid_input("corrupted input data (LZMA2:1)"));
+            } else if control >= 0xA0 {
+                if let Some(l) = self.lzma.as_mut() {
+                    l.reset()
+                }
+            }
+
+            self.body_target = compressed_size;
+            self.body_buf.clear();
+            self.rc_ready = false;
+        } else if control > 0x02 {
+            return Err(error_invalid_input("corrupted input data (LZMA2:2)"));
+        } else {
+            self.is_lzma_chunk = false;
+            self.uncompressed_size =
+                (((self.header_buf[1] as usize) << 8) | self.header_buf[2] as usize) + 1;
+        }
+        Ok(())
+    }
+
+    /// Re-creates the LZMA state from a props byte already in hand, instead
+    /// of reading it from a blocking reader.
+    fn decode_props(&mut self, props: u8) -> crate::Result<()> {
+        if props > (4 * 5 + 4) * 9 + 8 {
+            return Err(error_invalid_input("corrupted input data (LZMA2:3)"));
+        }
+        let pb = props / (9 * 5);
+        let props = props - pb * 9 * 5;
+        let lp = props / 9;
+        let lc = props - lp * 9;
+        if lc + lp > 4 {
+            return Err(error_invalid_input("corrupted input data (LZMA2:4)"));
+        }
+        self.lzma = Some(LzmaDecoder::new(lc as _, lp as _, pb as _));
+
+        Ok(())
+    }
+
+    /// Accumulates an LZMA chunk's compressed payload from `input` into
+    /// `body_buf` until it reaches `body_target` bytes.
+    ///
+    /// Returns `(consumed, complete)`, with the same consumed-is-always-
+    /// accurate guarantee as [`Self::feed_header`].
+    fn feed_body(&mut self, input: &[u8]) -> (usize, bool) {
+        let need = self.body_target - self.body_buf.len();
+        let take = need.min(input.len());
+        self.body_buf.extend_from_slice(&input[..take]);
+        (take, self.body_buf.len() == self.body_target)
+    }
+}
+
+fn header_len(control: u8) -> crate::Result<usize> {
+    if control == 0x00 {
+        Ok(1)
+    } else if control == 0x01 || control == 0x02 {
+        Ok(3)
+    } else if control < 0x80 {
+        Err(error_invalid_input("corrupted input data (LZMA2:2)"))
+    } else if control < 0xC0 {
+        Ok(5)
+    } else {
+        Ok(6)
+    }
+}
@@ -0,0 +1,193 @@
+use std::io::{Seek, SeekFrom};
+
+use super::{error_invalid_input, error_other, lzma2_reader::Lzma2Reader, Read};
+use crate::ByteReader;
+
+/// Random-access reader over an LZMA2 stream, built on the same "Reset
+/// Everything" chunk property that [`crate::Lzma2ParallelReader`] uses for
+/// parallel decoding.
+///
+/// [`Self::build_index`] scans the stream's chunk headers once (no range
+/// decoding) and records, for every reset-everything chunk, its compressed
+/// input offset and cumulative uncompressed output offset. From then on,
+/// [`Self::seek_to_uncompressed`] can jump to the nearest preceding reset
+/// point and decode-and-discard forward to the requested position, instead
+/// of decoding the whole stream from the start.
+///
+/// The index is cheap to build (it never runs the range coder) and small
+/// enough to cache: [`Self::build_index`] returns it as a plain
+/// `Vec<(u64, u64)>` of `(compressed_offset, uncompressed_offset)` pairs
+/// that callers can serialize alongside the compressed file and hand back
+/// via [`Self::set_index`] next time, skipping the scan entirely.
+pub struct Lzma2IndexedReader<R> {
+    active: Option<Lzma2Reader<R>>,
+    dict_size: u32,
+    preset_dict: Option<Vec<u8>>,
+    index: Vec<(u64, u64)>,
+    uncompressed_pos: u64,
+}
+
+impl<R: Read + Seek> Lzma2IndexedReader<R> {
+    /// Creates a new indexed reader, positioned at the start of the stream.
+    pub fn new(inner: R, dict_size: u32, preset_dict: Option<&[u8]>) -> Self {
+        Self {
+            active: Some(Lzma2Reader::new(inner, dict_size, preset_dict)),
+            dict_size,
+            preset_dict: preset_dict.map(|d| d.to_vec()),
+            index: Vec::new(),
+            uncompressed_pos: 0,
+        }
+    }
+
+    /// Supplies a previously built index, skipping the scan in
+    /// [`Self::build_index`]. The index must have come from
+    /// [`Self::build_index`] run over this exact compressed stream.
+    pub fn set_index(&mut self, index: Vec<(u64, u64)>) {
+        self.index = index;
+    }
+
+    /// Scans the stream's chunk headers and records, for every
+    /// reset-everything chunk, its `(compressed_offset, uncompressed_offset)`
+    /// pair. Leaves the reader positioned at the start of the stream
+    /// afterwards.
+    pub fn build_index(&mut self) -> crate::Result<Vec<(u64, u64)>> {
+        let inner = self.take_inner()?;
+        let mut inner = inner;
+        inner.seek(SeekFrom::Start(0)).map_err(error_other)?;
+
+        let mut index = Vec::new();
+        let mut compressed_pos: u64 = 0;
+        let mut uncompressed_pos: u64 = 0;
+
+        loop {
+            let control = inner.read_u8()?;
+            let chunk_start = compressed_pos;
+            compressed_pos += 1;
+
+            if control == 0x00 {
+                break;
+            }
+
+            let (uncompressed_len, is_reset_everything) = if control >= 0x80 {
+                let mut uncompressed_len = ((control & 0x1F) as u64) << 16;
+                uncompressed_len += inner.read_u16_be()? as u64 + 1;
+                let compressed_len = inner.read_u16_be()? as u64 + 1;
+                compressed_pos += 4;
+
+                if control >= 0xC0 {
+                    let _props = inner.read_u8()?;
+                    compressed_pos += 1;
+                }
+
+                inner
+                    .seek(SeekFrom::Current(compressed_len as i64))
+                    .map_err(error_other)?;
+                compressed_pos += compressed_len;
+
+                (uncompressed_len, control >= 0xE0)
+            } else if control == 0x01 || control == 0x02 {
+                let len = inner.read_u16_be()? as u64 + 1;
+                compressed_pos += 2;
+
+                inner
+                    .seek(SeekFrom::Current(len as i64))
+                    .map_err(error_other)?;
+                compressed_pos += len;
+
+                (len, control == 0x01)
+            } else {
+                return Err(error_invalid_input(
+                    "corrupted input data (LZMA2 index scan)",
+                ));
+            };
+
+            if is_reset_everything {
+                index.push((chunk_start, uncompressed_pos));
+            }
+            uncompressed_pos += uncompressed_len;
+        }
+
+        inner.seek(SeekFrom::Start(0)).map_err(error_other)?;
+        self.active = Some(Lzma2Reader::new(
+            inner,
+            self.dict_size,
+            self.preset_dict.as_deref(),
+        ));
+        self.uncompressed_pos = 0;
+        self.index = index.clone();
+
+        Ok(index)
+    }
+
+    /// Seeks so that the next [`Read::read`] call returns bytes starting at
+    /// uncompressed position `pos`.
+    ///
+    /// Builds the index on first use if [`Self::build_index`] or
+    /// [`Self::set_index`] hasn't been called yet.
+    pub fn seek_to_uncompressed(&mut self, pos: u64) -> crate::Result<()> {
+        if self.index.is_empty() {
+            self.build_index()?;
+        }
+
+        let idx = match self.index.binary_search_by(|(_, u)| u.cmp(&pos)) {
+            Ok(i) => Some(i),
+            Err(0) => None,
+            Err(i) => Some(i - 1),
+        };
+        // Every index entry is a reset-everything chunk, so decoding from it
+        // starts with an empty dictionary. With no entry at or before `pos`,
+        // decoding restarts from the start of the stream with the preset
+        // dict instead.
+        let (compressed_offset, uncompressed_offset, preset_dict) = match idx {
+            Some(i) => (self.index[i].0, self.index[i].1, None),
+            None => (0, 0, self.preset_dict.clone()),
+        };
+
+        let mut inner = self.take_inner()?;
+        inner
+            .seek(SeekFrom::Start(compressed_offset))
+            .map_err(error_other)?;
+
+        let mut reader = Lzma2Reader::new(inner, self.dict_size, preset_dict.as_deref());
+
+        let mut remaining = pos - uncompressed_offset;
+        let mut discard = [0u8; 4096];
+        while remaining > 0 {
+            let take = remaining.min(discard.len() as u64) as usize;
+            let n = reader.read(&mut discard[..take])?;
+            if n == 0 {
+                return Err(error_invalid_input("seek position past end of stream"));
+            }
+            remaining -= n as u64;
+        }
+
+        self.uncompressed_pos = pos;
+        self.active = Some(reader);
+        Ok(())
+    }
+
+    /// Returns the current uncompressed read position.
+    pub fn uncompressed_pos(&self) -> u64 {
+        self.uncompressed_pos
+    }
+
+    fn take_inner(&mut self) -> crate::Result<R> {
+        Ok(self
+            .active
+            .take()
+            .ok_or_else(|| error_other("indexed reader has no active stream"))?
+            .into_inner())
+    }
+}
+
+impl<R: Read + Seek> Read for Lzma2IndexedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> crate::Result<usize> {
+        let n = self
+            .active
+            .as_mut()
+            .ok_or_else(|| error_other("indexed reader has no active stream"))?
+            .read(buf)?;
+        self.uncompressed_pos += n as u64;
+        Ok(n)
+    }
+}
@@ -0,0 +1,263 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{error_invalid_input, lzma2_reader::Lzma2Reader, Read};
+
+/// Decodes an LZMA2 stream across multiple threads by splitting it at
+/// "Reset Everything" chunk boundaries (control bytes `0xE0`-`0xFF`, and the
+/// dictionary-resetting uncompressed chunk `0x01`).
+///
+/// Those chunks reset the dictionary, LZMA state, and properties, so
+/// everything from one such chunk up to (but not including) the next one
+/// can be decoded independently of what came before it. `Lzma2ParallelReader`
+/// exploits that: it first scans the stream's chunk headers only (no range
+/// decoding) to find the reset points, then hands each resulting segment to
+/// its own [`Lzma2Reader`] on a worker thread and concatenates the results
+/// in order.
+///
+/// If the stream turns out to contain a single segment (e.g. it never
+/// resets after the first chunk), this falls back to decoding it on the
+/// calling thread with the ordinary single-threaded path.
+///
+/// # Examples
+/// ```
+/// use lzma_rust2::{Lzma2ParallelReader, LzmaOptions};
+///
+/// let compressed: Vec<u8> = vec![
+///     1, 0, 12, 72, 101, 108, 108, 111, 44, 32, 119, 111, 114, 108, 100, 33, 0,
+/// ];
+/// let reader = Lzma2ParallelReader::new(&compressed, LzmaOptions::DICT_SIZE_DEFAULT, None);
+/// let decompressed = reader.decode().unwrap();
+/// assert_eq!(&decompressed[..], b"Hello, world!");
+/// ```
+pub struct Lzma2ParallelReader<'a> {
+    input: &'a [u8],
+    dict_size: u32,
+    preset_dict: Option<&'a [u8]>,
+}
+
+/// A lightweight chunk-header record produced by [`scan_chunks`], without
+/// running the range coder.
+struct ChunkRecord {
+    input_offset: usize,
+    input_len: usize,
+    uncompressed_len: usize,
+    is_reset_everything: bool,
+}
+
+impl<'a> Lzma2ParallelReader<'a> {
+    /// Creates a new parallel reader over an in-memory LZMA2 stream.
+    ///
+    /// `dict_size` and `preset_dict` mean the same as for [`Lzma2Reader`];
+    /// `preset_dict` only ever affects the first segment, since every later
+    /// segment begins at a chunk that resets the dictionary.
+    pub fn new(input: &'a [u8], dict_size: u32, preset_dict: Option<&'a [u8]>) -> Self {
+        Self {
+            input,
+            dict_size,
+            preset_dict,
+        }
+    }
+
+    /// Decodes the whole stream, returning the concatenated uncompressed
+    /// bytes.
+    pub fn decode(&self) -> crate::Result<Vec<u8>> {
+        let chunks = scan_chunks(self.input)?;
+        if chunks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let segments = build_segments(&chunks);
+        let total_out: usize = chunks.iter().map(|c| c.uncompressed_len).sum();
+
+        if segments.len() <= 1 {
+            // A single giant segment (or a stream that never resets after
+            // its first chunk) has no independent parallel work to do.
+            let mut reader = Lzma2Reader::new(self.input, self.dict_size, self.preset_dict);
+            let mut out = Vec::with_capacity(total_out);
+            read_to_end_into(&mut reader, &mut out)?;
+            return Ok(out);
+        }
+
+        let mut out = vec![0u8; total_out];
+        let seg_out_lens: Vec<usize> = segments
+            .iter()
+            .map(|seg| chunks[seg.clone()].iter().map(|c| c.uncompressed_len).sum())
+            .collect();
+
+        // Build each segment's (input, output, preset dict) up front, then
+        // hand them out through a shared work queue instead of spawning one
+        // thread per segment: a stream with many reset points could have
+        // far more segments than cores, and spawning a thread per segment
+        // would exhaust OS resources long before that helps throughput.
+        let mut work_items = Vec::with_capacity(segments.len());
+        let mut rest = out.as_mut_slice();
+        for (i, seg) in segments.iter().enumerate() {
+            let seg_start_in = chunks[seg.start].input_offset;
+            let last = &chunks[seg.end - 1];
+            let seg_end_in = last.input_offset + last.input_len;
+            let seg_input = &self.input[seg_start_in..seg_end_in];
+
+            let (seg_out, remainder) = rest.split_at_mut(seg_out_lens[i]);
+            rest = remainder;
+
+            // Only the first segment can depend on the caller-supplied
+            // preset dictionary; every later segment starts at a reset
+            // point and begins with an empty dictionary.
+            let preset_dict = if i == 0 { self.preset_dict } else { None };
+            work_items.push((seg_input, seg_out, preset_dict));
+        }
+
+        let num_workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(work_items.len());
+        let queue = std::sync::Mutex::new(work_items);
+        let dict_size = self.dict_size;
+
+        let results: Vec<crate::Result<()>> = std::thread::scope(|scope| {
+            let mut handles = Vec::with_capacity(num_workers);
+            for _ in 0..num_workers {
+                handles.push(scope.spawn(|| {
+                    let mut worker_results = Vec::new();
+                    while let Some((seg_input, seg_out, preset_dict)) = queue.lock().unwrap().pop()
+                    {
+                        let mut reader = Lzma2Reader::new(seg_input, dict_size, preset_dict);
+                        worker_results.push(read_exact_into(&mut reader, seg_out));
+                    }
+                    worker_results
+                }));
+            }
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+
+        for result in results {
+            result?;
+        }
+
+        Ok(out)
+    }
+}
+
+/// Scans an LZMA2 stream's chunk headers, recording just enough to know
+/// where each chunk starts/ends and whether it is a "Reset Everything"
+/// restart point, without running the range coder on its payload.
+fn scan_chunks(input: &[u8]) -> crate::Result<Vec<ChunkRecord>> {
+    let mut chunks = Vec::new();
+    let mut pos = 0usize;
+
+    loop {
+        let control = *input
+            .get(pos)
+            .ok_or_else(|| error_invalid_input("truncated LZMA2 stream"))?;
+        let chunk_start = pos;
+        pos += 1;
+
+        if control == 0x00 {
+            break;
+        }
+
+        if control >= 0x80 {
+            let size_bytes = input
+                .get(pos..pos + 4)
+                .ok_or_else(|| error_invalid_input("truncated LZMA2 chunk header"))?;
+            let uncompressed_len = (((control & 0x1F) as usize) << 16)
+                + (((size_bytes[0] as usize) << 8) | size_bytes[1] as usize)
+                + 1;
+            let compressed_len = (((size_bytes[2] as usize) << 8) | size_bytes[3] as usize) + 1;
+            pos += 4;
+
+            if control >= 0xC0 {
+                // Props byte, reset along with state and dictionary.
+                pos += 1;
+            }
+
+            let data_start = pos;
+            if data_start + compressed_len > input.len() {
+                return Err(error_invalid_input("truncated LZMA2 chunk data"));
+            }
+            pos = data_start + compressed_len;
+
+            chunks.push(ChunkRecord {
+                input_offset: chunk_start,
+                input_len: pos - chunk_start,
+                uncompressed_len,
+                is_reset_everything: control >= 0xE0,
+            });
+        } else if control == 0x01 || control == 0x02 {
+            let size_bytes = input
+                .get(pos..pos + 2)
+                .ok_or_else(|| error_invalid_input("truncated LZMA2 uncompressed chunk header"))?;
+            let uncompressed_len = (((size_bytes[0] as usize) << 8) | size_bytes[1] as usize) + 1;
+            pos += 2;
+
+            let data_start = pos;
+            if data_start + uncompressed_len > input.len() {
+                return Err(error_invalid_input(
+                    "truncated LZMA2 uncompressed chunk data",
+                ));
+            }
+            pos = data_start + uncompressed_len;
+
+            chunks.push(ChunkRecord {
+                input_offset: chunk_start,
+                input_len: pos - chunk_start,
+                uncompressed_len,
+                // 0x01 resets the dictionary; 0x02 preserves it and must
+                // stay attached to whatever segment precedes it.
+                is_reset_everything: control == 0x01,
+            });
+        } else {
+            return Err(error_invalid_input(
+                "corrupted input data (LZMA2 parallel scan)",
+            ));
+        }
+    }
+
+    Ok(chunks)
+}
+
+/// Groups chunk records into maximal runs that can be decoded
+/// independently: every run but the first begins at a "Reset Everything"
+/// chunk, and keeps every following non-resetting chunk attached to it.
+fn build_segments(chunks: &[ChunkRecord]) -> Vec<Range<usize>> {
+    let mut segments = Vec::new();
+    let mut seg_start = 0;
+
+    for i in 1..chunks.len() {
+        if chunks[i].is_reset_everything {
+            segments.push(seg_start..i);
+            seg_start = i;
+        }
+    }
+    segments.push(seg_start..chunks.len());
+
+    segments
+}
+
+fn read_exact_into<R: Read>(reader: &mut R, mut buf: &mut [u8]) -> crate::Result<()> {
+    while !buf.is_empty() {
+        let n = reader.read(buf)?;
+        if n == 0 {
+            return Err(error_invalid_input("unexpected end of LZMA2 segment"));
+        }
+        buf = &mut buf[n..];
+    }
+    Ok(())
+}
+
+fn read_to_end_into<R: Read>(reader: &mut R, out: &mut Vec<u8>) -> crate::Result<()> {
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = reader.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+        out.extend_from_slice(&chunk[..n]);
+    }
+    Ok(())
+}